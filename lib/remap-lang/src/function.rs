@@ -0,0 +1,168 @@
+use crate::{Expression, Path, Result, Value};
+use std::collections::HashMap;
+
+pub trait Function: std::fmt::Debug {
+    /// The identifier by which the function is called.
+    fn identifier(&self) -> &'static str;
+
+    /// The parameters this function accepts.
+    fn parameters(&self) -> &'static [Parameter] {
+        &[]
+    }
+
+    /// Compile the arguments this function was called with into an
+    /// `Expression` that can be executed at runtime.
+    fn compile(&self, arguments: ArgumentList) -> Result<Box<dyn Expression>>;
+}
+
+/// A single named parameter accepted by a `Function`.
+#[derive(Debug, Copy, Clone)]
+pub struct Parameter {
+    /// The keyword used to assign a value to this parameter, either by name
+    /// (`fn(keyword: value)`) or by position.
+    pub keyword: &'static str,
+
+    /// Checks whether a given `Value` is a valid value for this parameter.
+    pub accepts: fn(&Value) -> bool,
+
+    /// Whether the caller is required to provide this parameter.
+    pub required: bool,
+
+    /// When set, this parameter consumes all remaining positional arguments
+    /// instead of a single one, e.g. `only_fields(.foo, .bar, .baz)`. A
+    /// function may have at most one variadic parameter, and it must be the
+    /// last one listed in `Function::parameters`.
+    pub variadic: bool,
+}
+
+/// A single call-site argument, prior to being matched against a `Parameter`.
+#[derive(Debug)]
+pub enum Argument {
+    Expression(Box<dyn Expression>),
+    Path(Path),
+}
+
+/// The resolved arguments a `Function` was called with, keyed by parameter
+/// keyword, ready to be pulled out (and type-checked) during `compile`.
+#[derive(Debug, Clone, Default)]
+pub struct ArgumentList {
+    expressions: HashMap<&'static str, Box<dyn Expression>>,
+    paths: HashMap<&'static str, Path>,
+    variadic_paths: Vec<Path>,
+}
+
+impl ArgumentList {
+    /// Resolves a call's ordered, positional arguments against a function's
+    /// declared `Parameter`s. Arguments are matched one-to-one against
+    /// parameters by position, except once a `variadic` parameter is
+    /// reached: that parameter, and every argument after it, all collect
+    /// into `variadic_paths` rather than a single keyword slot.
+    pub fn from_arguments(
+        parameters: &'static [Parameter],
+        arguments: Vec<Argument>,
+    ) -> Result<Self> {
+        let mut list = ArgumentList::default();
+        let variadic_index = parameters.iter().position(|p| p.variadic);
+
+        for (index, argument) in arguments.into_iter().enumerate() {
+            if variadic_index.map_or(false, |variadic_index| index >= variadic_index) {
+                match argument {
+                    Argument::Path(path) => list.variadic_paths.push(path),
+                    Argument::Expression(_) => {
+                        return Err("variadic parameters only accept paths".into())
+                    }
+                }
+
+                continue;
+            }
+
+            let param = parameters
+                .get(index)
+                .ok_or_else(|| "too many arguments provided".to_string())?;
+
+            match argument {
+                Argument::Expression(expr) => {
+                    list.expressions.insert(param.keyword, expr);
+                }
+                Argument::Path(path) => {
+                    list.paths.insert(param.keyword, path);
+                }
+            }
+        }
+
+        Ok(list)
+    }
+
+    pub fn required_expr(&mut self, keyword: &str) -> Result<Box<dyn Expression>> {
+        self.expressions
+            .remove(keyword)
+            .ok_or_else(|| format!("missing required argument `{}`", keyword).into())
+    }
+
+    pub fn optional_expr(&mut self, keyword: &str) -> Result<Option<Box<dyn Expression>>> {
+        Ok(self.expressions.remove(keyword))
+    }
+
+    pub fn required_path(&mut self, keyword: &str) -> Result<Path> {
+        self.paths
+            .remove(keyword)
+            .ok_or_else(|| format!("missing required argument `{}`", keyword).into())
+    }
+
+    pub fn optional_path(&mut self, keyword: &str) -> Result<Option<Path>> {
+        Ok(self.paths.remove(keyword))
+    }
+
+    /// Returns every path assigned to this function's variadic parameter, in
+    /// call order. Unlike `required_path`/`optional_path`, this is not
+    /// bounded to a single keyword slot — it drains all positional arguments
+    /// `from_arguments` collected for the variadic parameter.
+    pub fn variadic_paths(&mut self, keyword: &str) -> Result<Vec<Path>> {
+        if self.variadic_paths.is_empty() {
+            return Err(format!("missing required argument `{}`", keyword).into());
+        }
+
+        Ok(std::mem::take(&mut self.variadic_paths))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parameters() -> &'static [Parameter] {
+        &[Parameter {
+            keyword: "fields",
+            accepts: |_| true,
+            required: true,
+            variadic: true,
+        }]
+    }
+
+    #[test]
+    fn variadic_paths_collects_all_positional_arguments() {
+        let arguments = vec![
+            Argument::Path(Path::from("foo")),
+            Argument::Path(Path::from("bar")),
+            Argument::Path(Path::from("baz")),
+        ];
+
+        let mut list = ArgumentList::from_arguments(parameters(), arguments).unwrap();
+
+        let paths = list
+            .variadic_paths("fields")
+            .unwrap()
+            .iter()
+            .map(Path::as_string)
+            .collect::<Vec<_>>();
+
+        assert_eq!(paths, vec!["foo", "bar", "baz"]);
+    }
+
+    #[test]
+    fn variadic_paths_errors_when_empty() {
+        let mut list = ArgumentList::from_arguments(parameters(), vec![]).unwrap();
+
+        assert!(list.variadic_paths("fields").is_err());
+    }
+}
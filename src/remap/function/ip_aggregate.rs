@@ -0,0 +1,257 @@
+use remap::prelude::*;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+
+use super::ip_subnet::{get_mask_bits, parse_subnet};
+
+#[derive(Clone, Copy, Debug)]
+pub struct IpAggregate;
+
+impl Function for IpAggregate {
+    fn identifier(&self) -> &'static str {
+        "ip_aggregate"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[Parameter {
+            keyword: "value",
+            accepts: |v| matches!(v, Value::Array(_)),
+            required: true,
+            variadic: false,
+        }]
+    }
+
+    fn compile(&self, mut arguments: ArgumentList) -> Result<Box<dyn Expression>> {
+        let value = arguments.required_expr("value")?;
+
+        Ok(Box::new(IpAggregateFn { value }))
+    }
+}
+
+#[derive(Debug, Clone)]
+struct IpAggregateFn {
+    value: Box<dyn Expression>,
+}
+
+impl IpAggregateFn {
+    #[cfg(test)]
+    fn new(value: Box<dyn Expression>) -> Self {
+        Self { value }
+    }
+}
+
+impl Expression for IpAggregateFn {
+    fn execute(&self, state: &mut state::Program, object: &mut dyn Object) -> Result<Value> {
+        let values = required!(state, object, self.value, Value::Array(v) => v);
+
+        let mut v4_ranges = Vec::new();
+        let mut v6_ranges = Vec::new();
+
+        for value in values {
+            let bytes = match value {
+                Value::String(bytes) => bytes,
+                _ => return Err("ip_aggregate elements must be strings".into()),
+            };
+
+            let cidr = String::from_utf8_lossy(&bytes);
+            let (start, end, is_v6) = parse_cidr_range(&cidr)?;
+
+            if is_v6 {
+                v6_ranges.push((start, end));
+            } else {
+                v4_ranges.push((start, end));
+            }
+        }
+
+        let mut result: Vec<Value> = aggregate_ranges(v4_ranges, 32)
+            .into_iter()
+            .map(Value::from)
+            .collect();
+        result.extend(aggregate_ranges(v6_ranges, 128).into_iter().map(Value::from));
+
+        Ok(Value::Array(result))
+    }
+
+    fn type_def(&self, state: &state::Compiler) -> TypeDef {
+        self.value
+            .type_def(state)
+            .fallible_unless(value::Kind::Array)
+            .with_constraint(value::Kind::Array)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::map;
+
+    remap::test_type_def![value_array {
+        expr: |_| IpAggregateFn {
+            value: Literal::from(Value::Array(vec![Value::from("192.168.0.0/24")])).boxed(),
+        },
+        def: TypeDef {
+            kind: value::Kind::Array,
+            ..Default::default()
+        },
+    }];
+
+    #[test]
+    fn ip_aggregate() {
+        let cases = vec![
+            (
+                map![],
+                Ok(Value::Array(vec![Value::from("192.168.0.0/23")])),
+                IpAggregateFn::new(Box::new(Literal::from(Value::Array(vec![
+                    Value::from("192.168.0.0/24"),
+                    Value::from("192.168.1.0/24"),
+                ])))),
+            ),
+            (
+                map![],
+                Ok(Value::Array(vec![Value::from("192.168.0.0/24")])),
+                IpAggregateFn::new(Box::new(Literal::from(Value::Array(vec![
+                    Value::from("192.168.0.0/25"),
+                    Value::from("192.168.0.128/25"),
+                ])))),
+            ),
+        ];
+
+        let mut state = state::Program::default();
+
+        for (mut object, exp, func) in cases {
+            let got = func
+                .execute(&mut state, &mut object)
+                .map_err(|e| format!("{:#}", anyhow::anyhow!(e)));
+
+            assert_eq!(got, exp);
+        }
+    }
+}
+
+/// Parses a CIDR string (`a.b.c.d/n`, either v4 or v6) into its integer
+/// `[start, end]` range, along with whether it belongs to the IPv6 family.
+fn parse_cidr_range(cidr: &str) -> Result<(u128, u128, bool)> {
+    let mut parts = cidr.splitn(2, '/');
+    let addr = parts
+        .next()
+        .ok_or_else(|| format!("{} is not a valid CIDR prefix", cidr))?;
+    let subnet = parts
+        .next()
+        .ok_or_else(|| format!("{} is missing a prefix length", cidr))?;
+
+    let addr: IpAddr = addr
+        .parse()
+        .map_err(|err| format!("unable to parse IP address: {}", err))?;
+    let prefix = parse_subnet(&format!("/{}", subnet))?;
+
+    match addr {
+        IpAddr::V4(addr) => {
+            if prefix > 32 {
+                return Err("subnet cannot be greater than 32 for ipv4 addresses".into());
+            }
+
+            let netmask = to_u32(&get_mask_bits(prefix, 4));
+            let start: u32 = u32::from(addr) & netmask;
+            let hostmask = !netmask;
+
+            Ok((start as u128, (start | hostmask) as u128, false))
+        }
+        IpAddr::V6(addr) => {
+            if prefix > 128 {
+                return Err("subnet cannot be greater than 128 for ipv6 addresses".into());
+            }
+
+            let netmask = to_u128(&get_mask_bits(prefix, 16));
+            let start = u128::from_be_bytes(addr.octets()) & netmask;
+            let hostmask = !netmask;
+
+            Ok((start, start | hostmask, true))
+        }
+    }
+}
+
+fn to_u32(bytes: &[u8]) -> u32 {
+    u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]])
+}
+
+fn to_u128(bytes: &[u8]) -> u128 {
+    let mut arr = [0u8; 16];
+    arr.copy_from_slice(bytes);
+    u128::from_be_bytes(arr)
+}
+
+/// Sorts and merges overlapping or adjacent `[start, end]` ranges.
+fn merge_ranges(mut ranges: Vec<(u128, u128)>) -> Vec<(u128, u128)> {
+    ranges.sort_by(|a, b| a.0.cmp(&b.0).then(a.1.cmp(&b.1)));
+
+    let mut merged: Vec<(u128, u128)> = Vec::new();
+
+    for (start, end) in ranges {
+        match merged.last_mut() {
+            Some((_, last_end)) if start <= last_end.saturating_add(1) => {
+                *last_end = (*last_end).max(end);
+            }
+            _ => merged.push((start, end)),
+        }
+    }
+
+    merged
+}
+
+/// Decomposes a single merged `[lo, hi]` range into the minimal set of CIDR
+/// blocks that exactly cover it, by repeatedly taking the largest block that
+/// both starts at `lo` and stays within `hi`.
+fn decompose_range(mut lo: u128, hi: u128, bits: u32) -> Vec<(u128, u32)> {
+    // The whole 128-bit space is a single `/0` block, whose size does not fit
+    // in a `u128`.
+    if lo == 0 && hi == u128::MAX {
+        return vec![(0, 0)];
+    }
+
+    let mut blocks = Vec::new();
+
+    while lo <= hi {
+        let align = if lo == 0 {
+            u128::MAX
+        } else {
+            lo & lo.wrapping_neg()
+        };
+        let span = largest_power_of_two_le(hi.saturating_sub(lo).saturating_add(1));
+        let size = align.min(span);
+        let prefix_len = bits - size.trailing_zeros().min(bits);
+
+        blocks.push((lo, prefix_len));
+
+        match lo.checked_add(size) {
+            Some(next) => lo = next,
+            None => break,
+        }
+    }
+
+    blocks
+}
+
+/// Returns the largest power of two less than or equal to `n`, or 1 when `n`
+/// is 0.
+fn largest_power_of_two_le(n: u128) -> u128 {
+    if n == 0 {
+        1
+    } else {
+        1u128 << (127 - n.leading_zeros())
+    }
+}
+
+fn aggregate_ranges(ranges: Vec<(u128, u128)>, bits: u32) -> Vec<String> {
+    merge_ranges(ranges)
+        .into_iter()
+        .flat_map(|(lo, hi)| decompose_range(lo, hi, bits))
+        .map(|(addr, prefix_len)| format_cidr(addr, prefix_len, bits))
+        .collect()
+}
+
+fn format_cidr(addr: u128, prefix_len: u32, bits: u32) -> String {
+    if bits == 32 {
+        format!("{}/{}", Ipv4Addr::from(addr as u32), prefix_len)
+    } else {
+        format!("{}/{}", Ipv6Addr::from(addr.to_be_bytes()), prefix_len)
+    }
+}
@@ -0,0 +1,181 @@
+use remap::prelude::*;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+
+use super::ip_subnet::{get_mask_bits, ipv4_addr, ipv6_addr, mask_ips, parse_subnet};
+
+#[derive(Clone, Copy, Debug)]
+pub struct IpCidrContains;
+
+impl Function for IpCidrContains {
+    fn identifier(&self) -> &'static str {
+        "ip_cidr_contains"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[
+            Parameter {
+                keyword: "value",
+                accepts: |v| matches!(v, Value::String(_)),
+                required: true,
+                variadic: false,
+            },
+            Parameter {
+                keyword: "cidr",
+                accepts: |v| matches!(v, Value::String(_)),
+                required: true,
+                variadic: false,
+            },
+        ]
+    }
+
+    fn compile(&self, mut arguments: ArgumentList) -> Result<Box<dyn Expression>> {
+        let value = arguments.required_expr("value")?;
+        let cidr = arguments.required_expr("cidr")?;
+
+        Ok(Box::new(IpCidrContainsFn { value, cidr }))
+    }
+}
+
+#[derive(Debug, Clone)]
+struct IpCidrContainsFn {
+    value: Box<dyn Expression>,
+    cidr: Box<dyn Expression>,
+}
+
+impl IpCidrContainsFn {
+    #[cfg(test)]
+    fn new(value: Box<dyn Expression>, cidr: Box<dyn Expression>) -> Self {
+        Self { value, cidr }
+    }
+}
+
+impl Expression for IpCidrContainsFn {
+    fn execute(&self, state: &mut state::Program, object: &mut dyn Object) -> Result<Value> {
+        let value: IpAddr = {
+            let bytes = required!(state, object, self.value, Value::String(v) => v);
+            String::from_utf8_lossy(&bytes)
+                .parse()
+                .map_err(|err| format!("unable to parse IP address: {}", err))
+        }?;
+
+        let cidr = {
+            let bytes = required!(state, object, self.cidr, Value::String(v) => v);
+            String::from_utf8_lossy(&bytes).into_owned()
+        };
+
+        let prefix = parse_subnet(&cidr)?;
+
+        let network_part = cidr.splitn(2, '/').next().filter(|addr| !addr.is_empty());
+        let network: IpAddr = match network_part {
+            Some(addr) => addr
+                .parse()
+                .map_err(|err| format!("unable to parse IP address: {}", err))?,
+            None => match value {
+                IpAddr::V4(_) => Ipv4Addr::UNSPECIFIED.into(),
+                IpAddr::V6(_) => Ipv6Addr::UNSPECIFIED.into(),
+            },
+        };
+
+        let contains = match (value, network) {
+            (IpAddr::V4(_), IpAddr::V4(_)) => {
+                if prefix > 32 {
+                    return Err("subnet cannot be greater than 32 for ipv4 addresses".into());
+                }
+
+                let mask = ipv4_addr(get_mask_bits(prefix, 4));
+                mask_ips(value, mask)? == mask_ips(network, mask)?
+            }
+            (IpAddr::V6(_), IpAddr::V6(_)) => {
+                if prefix > 128 {
+                    return Err("subnet cannot be greater than 128 for ipv6 addresses".into());
+                }
+
+                let mask = ipv6_addr(get_mask_bits(prefix, 16));
+                mask_ips(value, mask)? == mask_ips(network, mask)?
+            }
+            // `value` and the prefix belong to different address families, so
+            // `value` can never be a member of `cidr`.
+            _ => false,
+        };
+
+        Ok(Value::Boolean(contains))
+    }
+
+    fn type_def(&self, state: &state::Compiler) -> TypeDef {
+        self.value
+            .type_def(state)
+            .fallible_unless(value::Kind::String)
+            .merge(
+                self.cidr
+                    .type_def(state)
+                    .fallible_unless(value::Kind::String),
+            )
+            .with_constraint(value::Kind::Boolean)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::map;
+
+    remap::test_type_def![value_string {
+        expr: |_| IpCidrContainsFn {
+            value: Literal::from("192.168.0.1").boxed(),
+            cidr: Literal::from("192.168.0.0/16").boxed(),
+        },
+        def: TypeDef {
+            kind: value::Kind::Boolean,
+            ..Default::default()
+        },
+    }];
+
+    #[test]
+    fn ip_cidr_contains() {
+        let cases = vec![
+            (
+                map!["foo": "192.168.10.23"],
+                Ok(Value::from(true)),
+                IpCidrContainsFn::new(
+                    Box::new(Path::from("foo")),
+                    Box::new(Literal::from("192.168.0.0/16")),
+                ),
+            ),
+            (
+                map!["foo": "192.168.10.23"],
+                Ok(Value::from(false)),
+                IpCidrContainsFn::new(
+                    Box::new(Path::from("foo")),
+                    Box::new(Literal::from("10.0.0.0/8")),
+                ),
+            ),
+            (
+                map!["foo": "2404:6800:4003:c02::64"],
+                Ok(Value::from(true)),
+                IpCidrContainsFn::new(
+                    Box::new(Path::from("foo")),
+                    Box::new(Literal::from("2404:6800::/32")),
+                ),
+            ),
+            (
+                map!["foo": "192.168.10.23"],
+                Ok(Value::from(false)),
+                IpCidrContainsFn::new(
+                    Box::new(Path::from("foo")),
+                    Box::new(Literal::from("2404:6800::/32")),
+                ),
+            ),
+        ];
+
+        let mut state = state::Program::default();
+
+        for (mut object, exp, func) in cases {
+            let got = func
+                .execute(&mut state, &mut object)
+                .map_err(|e| format!("{:#}", anyhow::anyhow!(e)));
+
+            assert_eq!(got, exp);
+        }
+    }
+}
+
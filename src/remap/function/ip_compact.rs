@@ -0,0 +1,102 @@
+use remap::prelude::*;
+use std::net::IpAddr;
+
+#[derive(Clone, Copy, Debug)]
+pub struct IpCompact;
+
+impl Function for IpCompact {
+    fn identifier(&self) -> &'static str {
+        "ip_compact"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[Parameter {
+            keyword: "value",
+            accepts: |v| matches!(v, Value::String(_)),
+            required: true,
+            variadic: false,
+        }]
+    }
+
+    fn compile(&self, mut arguments: ArgumentList) -> Result<Box<dyn Expression>> {
+        let value = arguments.required_expr("value")?;
+
+        Ok(Box::new(IpCompactFn { value }))
+    }
+}
+
+#[derive(Debug, Clone)]
+struct IpCompactFn {
+    value: Box<dyn Expression>,
+}
+
+impl IpCompactFn {
+    #[cfg(test)]
+    fn new(value: Box<dyn Expression>) -> Self {
+        Self { value }
+    }
+}
+
+impl Expression for IpCompactFn {
+    fn execute(&self, state: &mut state::Program, object: &mut dyn Object) -> Result<Value> {
+        let value: IpAddr = {
+            let bytes = required!(state, object, self.value, Value::String(v) => v);
+            String::from_utf8_lossy(&bytes)
+                .parse()
+                .map_err(|err| format!("unable to parse IP address: {}", err))
+        }?;
+
+        // `Ipv6Addr`'s `Display` impl already produces the RFC 5952 canonical
+        // compressed form, so for v6 this just re-serializes the address.
+        Ok(Value::from(value.to_string()))
+    }
+
+    fn type_def(&self, state: &state::Compiler) -> TypeDef {
+        self.value
+            .type_def(state)
+            .fallible_unless(value::Kind::String)
+            .with_constraint(value::Kind::String)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::map;
+
+    remap::test_type_def![value_string {
+        expr: |_| IpCompactFn {
+            value: Literal::from("2404:6800:4003:0c02:0000:0000:0000:0064").boxed(),
+        },
+        def: TypeDef {
+            kind: value::Kind::String,
+            ..Default::default()
+        },
+    }];
+
+    #[test]
+    fn ip_compact() {
+        let cases = vec![
+            (
+                map!["foo": "2404:6800:4003:0c02:0000:0000:0000:0064"],
+                Ok(Value::from("2404:6800:4003:c02::64")),
+                IpCompactFn::new(Box::new(Path::from("foo"))),
+            ),
+            (
+                map!["foo": "192.168.0.1"],
+                Ok(Value::from("192.168.0.1")),
+                IpCompactFn::new(Box::new(Path::from("foo"))),
+            ),
+        ];
+
+        let mut state = state::Program::default();
+
+        for (mut object, exp, func) in cases {
+            let got = func
+                .execute(&mut state, &mut object)
+                .map_err(|e| format!("{:#}", anyhow::anyhow!(e)));
+
+            assert_eq!(got, exp);
+        }
+    }
+}
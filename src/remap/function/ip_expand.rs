@@ -0,0 +1,112 @@
+use remap::prelude::*;
+use std::net::IpAddr;
+
+#[derive(Clone, Copy, Debug)]
+pub struct IpExpand;
+
+impl Function for IpExpand {
+    fn identifier(&self) -> &'static str {
+        "ip_expand"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[Parameter {
+            keyword: "value",
+            accepts: |v| matches!(v, Value::String(_)),
+            required: true,
+            variadic: false,
+        }]
+    }
+
+    fn compile(&self, mut arguments: ArgumentList) -> Result<Box<dyn Expression>> {
+        let value = arguments.required_expr("value")?;
+
+        Ok(Box::new(IpExpandFn { value }))
+    }
+}
+
+#[derive(Debug, Clone)]
+struct IpExpandFn {
+    value: Box<dyn Expression>,
+}
+
+impl IpExpandFn {
+    #[cfg(test)]
+    fn new(value: Box<dyn Expression>) -> Self {
+        Self { value }
+    }
+}
+
+impl Expression for IpExpandFn {
+    fn execute(&self, state: &mut state::Program, object: &mut dyn Object) -> Result<Value> {
+        let value: IpAddr = {
+            let bytes = required!(state, object, self.value, Value::String(v) => v);
+            String::from_utf8_lossy(&bytes)
+                .parse()
+                .map_err(|err| format!("unable to parse IP address: {}", err))
+        }?;
+
+        let expanded = match value {
+            IpAddr::V4(addr) => addr.to_string(),
+            IpAddr::V6(addr) => addr
+                .segments()
+                .iter()
+                .map(|segment| format!("{:04x}", segment))
+                .collect::<Vec<_>>()
+                .join(":"),
+        };
+
+        Ok(Value::from(expanded))
+    }
+
+    fn type_def(&self, state: &state::Compiler) -> TypeDef {
+        self.value
+            .type_def(state)
+            .fallible_unless(value::Kind::String)
+            .with_constraint(value::Kind::String)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::map;
+
+    remap::test_type_def![value_string {
+        expr: |_| IpExpandFn {
+            value: Literal::from("2404:6800:4003:c02::64").boxed(),
+        },
+        def: TypeDef {
+            kind: value::Kind::String,
+            ..Default::default()
+        },
+    }];
+
+    #[test]
+    fn ip_expand() {
+        let cases = vec![
+            (
+                map!["foo": "2404:6800:4003:c02::64"],
+                Ok(Value::from(
+                    "2404:6800:4003:0c02:0000:0000:0000:0064",
+                )),
+                IpExpandFn::new(Box::new(Path::from("foo"))),
+            ),
+            (
+                map!["foo": "192.168.0.1"],
+                Ok(Value::from("192.168.0.1")),
+                IpExpandFn::new(Box::new(Path::from("foo"))),
+            ),
+        ];
+
+        let mut state = state::Program::default();
+
+        for (mut object, exp, func) in cases {
+            let got = func
+                .execute(&mut state, &mut object)
+                .map_err(|e| format!("{:#}", anyhow::anyhow!(e)));
+
+            assert_eq!(got, exp);
+        }
+    }
+}
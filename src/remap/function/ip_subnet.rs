@@ -17,11 +17,19 @@ impl Function for IpSubnet {
                 keyword: "value",
                 accepts: |v| matches!(v, Value::String(_)),
                 required: true,
+                variadic: false,
             },
             Parameter {
                 keyword: "subnet",
                 accepts: |v| matches!(v, Value::String(_)),
                 required: true,
+                variadic: false,
+            },
+            Parameter {
+                keyword: "format",
+                accepts: |v| matches!(v, Value::String(_)),
+                required: false,
+                variadic: false,
             },
         ]
     }
@@ -29,8 +37,13 @@ impl Function for IpSubnet {
     fn compile(&self, mut arguments: ArgumentList) -> Result<Box<dyn Expression>> {
         let value = arguments.required_expr("value")?;
         let subnet = arguments.required_expr("subnet")?;
+        let format = arguments.optional_expr("format")?;
 
-        Ok(Box::new(IpSubnetFn { value, subnet }))
+        Ok(Box::new(IpSubnetFn {
+            value,
+            subnet,
+            format,
+        }))
     }
 }
 
@@ -38,12 +51,30 @@ impl Function for IpSubnet {
 struct IpSubnetFn {
     value: Box<dyn Expression>,
     subnet: Box<dyn Expression>,
+    format: Option<Box<dyn Expression>>,
 }
 
 impl IpSubnetFn {
     #[cfg(test)]
     fn new(value: Box<dyn Expression>, subnet: Box<dyn Expression>) -> Self {
-        Self { value, subnet }
+        Self {
+            value,
+            subnet,
+            format: None,
+        }
+    }
+
+    #[cfg(test)]
+    fn new_with_format(
+        value: Box<dyn Expression>,
+        subnet: Box<dyn Expression>,
+        format: Box<dyn Expression>,
+    ) -> Self {
+        Self {
+            value,
+            subnet,
+            format: Some(format),
+        }
     }
 }
 
@@ -56,15 +87,23 @@ impl Expression for IpSubnetFn {
                 .map_err(|err| format!("unable to parse IP address: {}", err))
         }?;
 
+        // Normalize IPv4-mapped IPv6 addresses (`::ffff:a.b.c.d`) to plain
+        // IPv4 up front, so a `/n` subnet is resolved against the address's
+        // real family rather than always matching the textual one.
+        let value = match value {
+            IpAddr::V6(addr) => ipv4_mapped(addr).map_or(value, IpAddr::V4),
+            IpAddr::V4(_) => value,
+        };
+
         let mask = {
             let bytes = required!(state, object, self.subnet, Value::String(v) => v);
             String::from_utf8_lossy(&bytes).into_owned()
         };
 
-        let mask = if mask.starts_with("/") {
+        let (mask, prefix_len) = if mask.starts_with("/") {
             // The parameter is a subnet.
             let subnet = parse_subnet(&mask)?;
-            match value {
+            let mask = match value {
                 IpAddr::V4(_) => {
                     if subnet > 32 {
                         return Err("subnet cannot be greater than 32 for ipv4 addresses".into());
@@ -79,26 +118,57 @@ impl Expression for IpSubnetFn {
 
                     ipv6_addr(get_mask_bits(subnet, 16))
                 }
-            }
+            };
+
+            (mask, subnet)
         } else {
             // The parameter is a mask.
-            mask.parse()
-                .map_err(|err| format!("unable to parse mask: {}", err))?
+            let mask: IpAddr = mask
+                .parse()
+                .map_err(|err| format!("unable to parse mask: {}", err))?;
+            let prefix_len = mask_to_prefix_len(mask)?;
+
+            (mask, prefix_len)
         };
 
-        Ok(Value::from(mask_ips(value, mask)?.to_string()))
+        let format = match &self.format {
+            Some(expr) => {
+                let bytes = required!(state, object, expr, Value::String(v) => v);
+                String::from_utf8_lossy(&bytes).into_owned()
+            }
+            None => "address".to_owned(),
+        };
+
+        let masked = mask_ips(value, mask)?;
+
+        match format.as_str() {
+            "address" => Ok(Value::from(masked.to_string())),
+            "cidr" => Ok(Value::from(format!("{}/{}", masked, prefix_len))),
+            _ => Err(format!(
+                "unknown format `{}`, expected `address` or `cidr`",
+                format
+            )
+            .into()),
+        }
     }
 
     fn type_def(&self, state: &state::Compiler) -> TypeDef {
-        self.value
+        let type_def = self
+            .value
             .type_def(state)
             .fallible_unless(value::Kind::String)
             .merge(
                 self.subnet
                     .type_def(state)
                     .fallible_unless(value::Kind::String),
-            )
-            .with_constraint(value::Kind::String)
+            );
+
+        let type_def = match &self.format {
+            Some(format) => type_def.merge(format.type_def(state).fallible_unless(value::Kind::String)),
+            None => type_def,
+        };
+
+        type_def.with_constraint(value::Kind::String)
     }
 }
 
@@ -158,6 +228,11 @@ mod tests {
                 Ok(Value::from("192.160.0.0")),
                 IpSubnetFn::new(Box::new(Path::from("foo")), Box::new(Literal::from("/12"))),
             ),
+            (
+                map!["foo": "::ffff:192.168.10.23"],
+                Ok(Value::from("192.168.0.0")),
+                IpSubnetFn::new(Box::new(Path::from("foo")), Box::new(Literal::from("/16"))),
+            ),
             (
                 map!["foo": "2404:6800:4003:c02::64"],
                 Ok(Value::from("2404:6800::")),
@@ -175,10 +250,53 @@ mod tests {
             assert_eq!(got, exp);
         }
     }
+
+    #[test]
+    fn ip_subnet_cidr_format() {
+        let cases = vec![
+            (
+                map!["foo": "192.168.10.23"],
+                Ok(Value::from("192.168.0.0/16")),
+                IpSubnetFn::new_with_format(
+                    Box::new(Path::from("foo")),
+                    Box::new(Literal::from("/16")),
+                    Box::new(Literal::from("cidr")),
+                ),
+            ),
+            (
+                map!["foo": "192.168.10.23"],
+                Ok(Value::from("192.168.0.0/16")),
+                IpSubnetFn::new_with_format(
+                    Box::new(Path::from("foo")),
+                    Box::new(Literal::from("255.255.0.0")),
+                    Box::new(Literal::from("cidr")),
+                ),
+            ),
+            (
+                map!["foo": "192.168.10.23"],
+                Err("255.0.255.0 is not a valid contiguous netmask".to_string()),
+                IpSubnetFn::new_with_format(
+                    Box::new(Path::from("foo")),
+                    Box::new(Literal::from("255.0.255.0")),
+                    Box::new(Literal::from("cidr")),
+                ),
+            ),
+        ];
+
+        let mut state = state::Program::default();
+
+        for (mut object, exp, func) in cases {
+            let got = func
+                .execute(&mut state, &mut object)
+                .map_err(|e| format!("{:#}", anyhow::anyhow!(e)));
+
+            assert_eq!(got, exp);
+        }
+    }
 }
 
 /// Parses a subnet in the form "/8" returns the number.
-fn parse_subnet(subnet: &str) -> Result<u32> {
+pub(crate) fn parse_subnet(subnet: &str) -> Result<u32> {
     let re = Regex::new(r"/(?P<subnet>\d*)").unwrap();
     let subnet = re
         .captures(subnet)
@@ -190,7 +308,11 @@ fn parse_subnet(subnet: &str) -> Result<u32> {
 }
 
 /// Masks the address by performing a bitwise AND between the two addresses.
-fn mask_ips(ip: IpAddr, mask: IpAddr) -> Result<IpAddr> {
+///
+/// An IPv4-mapped IPv6 address (e.g. `::ffff:192.168.1.1`) meeting a mask of
+/// the other family is transparently unwrapped to its `Ipv4Addr` first, so
+/// dual-stack pipelines don't need to normalize addresses by hand.
+pub(crate) fn mask_ips(ip: IpAddr, mask: IpAddr) -> Result<IpAddr> {
     match (ip, mask) {
         (IpAddr::V4(addr), IpAddr::V4(mask)) => {
             let addr: u32 = addr.into();
@@ -205,18 +327,34 @@ fn mask_ips(ip: IpAddr, mask: IpAddr) -> Result<IpAddr> {
 
             Ok(IpAddr::from(masked))
         }
-        (IpAddr::V6(_), IpAddr::V4(_)) => {
-            Err("attempting to mask an ipv6 address with an ipv4 mask".into())
-        }
-        (IpAddr::V4(_), IpAddr::V6(_)) => {
-            Err("attempting to mask an ipv4 address with an ipv6 mask".into())
-        }
+        (IpAddr::V6(addr), IpAddr::V4(_)) => match ipv4_mapped(addr) {
+            Some(addr) => mask_ips(addr.into(), mask),
+            None => Err("attempting to mask an ipv6 address with an ipv4 mask".into()),
+        },
+        (IpAddr::V4(_), IpAddr::V6(mask)) => match ipv4_mapped(mask) {
+            Some(mask) => mask_ips(ip, mask.into()),
+            None => Err("attempting to mask an ipv4 address with an ipv6 mask".into()),
+        },
+    }
+}
+
+/// Returns the `Ipv4Addr` an IPv4-mapped IPv6 address (`::ffff:a.b.c.d`)
+/// wraps, or `None` if `addr` isn't in that form.
+pub(crate) fn ipv4_mapped(addr: Ipv6Addr) -> Option<Ipv4Addr> {
+    match addr.segments() {
+        [0, 0, 0, 0, 0, 0xffff, hi, lo] => Some(Ipv4Addr::new(
+            (hi >> 8) as u8,
+            hi as u8,
+            (lo >> 8) as u8,
+            lo as u8,
+        )),
+        _ => None,
     }
 }
 
 /// Returns a vector with the left `subnet_bits` set to 1,
 /// The remaining are set to 0, to make up a total length of `bytes`.
-fn get_mask_bits(mut subnet_bits: u32, bytes: usize) -> Vec<u8> {
+pub(crate) fn get_mask_bits(mut subnet_bits: u32, bytes: usize) -> Vec<u8> {
     let mut mask = Vec::with_capacity(bytes);
 
     while subnet_bits > 0 {
@@ -234,15 +372,43 @@ fn get_mask_bits(mut subnet_bits: u32, bytes: usize) -> Vec<u8> {
     mask
 }
 
+/// Derives the prefix length from a dotted (v4) or colon (v6) netmask,
+/// erroring if the mask isn't contiguous (a run of one bits followed by a
+/// run of zero bits).
+fn mask_to_prefix_len(mask: IpAddr) -> Result<u32> {
+    match mask {
+        IpAddr::V4(addr) => {
+            let bits: u32 = addr.into();
+            let prefix = bits.leading_ones();
+
+            if bits.checked_shl(prefix).unwrap_or(0) != 0 {
+                return Err(format!("{} is not a valid contiguous netmask", addr).into());
+            }
+
+            Ok(prefix)
+        }
+        IpAddr::V6(addr) => {
+            let bits = u128::from_be_bytes(addr.octets());
+            let prefix = bits.leading_ones();
+
+            if bits.checked_shl(prefix).unwrap_or(0) != 0 {
+                return Err(format!("{} is not a valid contiguous netmask", addr).into());
+            }
+
+            Ok(prefix)
+        }
+    }
+}
+
 /// Take a vector of 4 bytes and returns an ipv4 IpAddr.
-fn ipv4_addr(vec: Vec<u8>) -> IpAddr {
+pub(crate) fn ipv4_addr(vec: Vec<u8>) -> IpAddr {
     debug_assert!(vec.len() == 4);
     Ipv4Addr::new(vec[0], vec[1], vec[2], vec[3]).into()
 }
 
 /// Take a vector of 16 bytes and returns an ipv6 IpAddr.
 /// This can be made nicer in [1.48](https://blog.rust-lang.org/2020/11/19/Rust-1.48.html#library-changes)
-fn ipv6_addr(vec: Vec<u8>) -> IpAddr {
+pub(crate) fn ipv6_addr(vec: Vec<u8>) -> IpAddr {
     debug_assert!(vec.len() == 16);
     Ipv6Addr::from([
         vec[0], vec[1], vec[2], vec[3], vec[4], vec[5], vec[6], vec[7], vec[8], vec[9], vec[10],
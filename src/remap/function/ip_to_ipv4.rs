@@ -0,0 +1,113 @@
+use remap::prelude::*;
+use std::net::IpAddr;
+
+use super::ip_subnet::ipv4_mapped;
+
+#[derive(Clone, Copy, Debug)]
+pub struct IpToIpv4;
+
+impl Function for IpToIpv4 {
+    fn identifier(&self) -> &'static str {
+        "ip_to_ipv4"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[Parameter {
+            keyword: "value",
+            accepts: |v| matches!(v, Value::String(_)),
+            required: true,
+            variadic: false,
+        }]
+    }
+
+    fn compile(&self, mut arguments: ArgumentList) -> Result<Box<dyn Expression>> {
+        let value = arguments.required_expr("value")?;
+
+        Ok(Box::new(IpToIpv4Fn { value }))
+    }
+}
+
+#[derive(Debug, Clone)]
+struct IpToIpv4Fn {
+    value: Box<dyn Expression>,
+}
+
+impl IpToIpv4Fn {
+    #[cfg(test)]
+    fn new(value: Box<dyn Expression>) -> Self {
+        Self { value }
+    }
+}
+
+impl Expression for IpToIpv4Fn {
+    fn execute(&self, state: &mut state::Program, object: &mut dyn Object) -> Result<Value> {
+        let value: IpAddr = {
+            let bytes = required!(state, object, self.value, Value::String(v) => v);
+            String::from_utf8_lossy(&bytes)
+                .parse()
+                .map_err(|err| format!("unable to parse IP address: {}", err))
+        }?;
+
+        let addr = match value {
+            IpAddr::V4(addr) => addr,
+            IpAddr::V6(addr) => ipv4_mapped(addr)
+                .ok_or_else(|| format!("{} is not an IPv4-mapped IPv6 address", addr))?,
+        };
+
+        Ok(Value::from(addr.to_string()))
+    }
+
+    fn type_def(&self, state: &state::Compiler) -> TypeDef {
+        self.value
+            .type_def(state)
+            .fallible_unless(value::Kind::String)
+            .with_constraint(value::Kind::String)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::map;
+
+    remap::test_type_def![value_string {
+        expr: |_| IpToIpv4Fn {
+            value: Literal::from("::ffff:192.168.0.1").boxed(),
+        },
+        def: TypeDef {
+            kind: value::Kind::String,
+            ..Default::default()
+        },
+    }];
+
+    #[test]
+    fn ip_to_ipv4() {
+        let cases = vec![
+            (
+                map!["foo": "::ffff:192.168.0.1"],
+                Ok(Value::from("192.168.0.1")),
+                IpToIpv4Fn::new(Box::new(Path::from("foo"))),
+            ),
+            (
+                map!["foo": "192.168.0.1"],
+                Ok(Value::from("192.168.0.1")),
+                IpToIpv4Fn::new(Box::new(Path::from("foo"))),
+            ),
+            (
+                map!["foo": "2404:6800:4003:c02::64"],
+                Err("2404:6800:4003:c02::64 is not an IPv4-mapped IPv6 address".to_string()),
+                IpToIpv4Fn::new(Box::new(Path::from("foo"))),
+            ),
+        ];
+
+        let mut state = state::Program::default();
+
+        for (mut object, exp, func) in cases {
+            let got = func
+                .execute(&mut state, &mut object)
+                .map_err(|e| format!("{:#}", anyhow::anyhow!(e)));
+
+            assert_eq!(got, exp);
+        }
+    }
+}